@@ -0,0 +1,49 @@
+use crate::engine::{default_sites, Engine};
+use structopt::StructOpt;
+
+/// Command line arguments for `hors`.
+#[derive(StructOpt, Debug)]
+#[structopt(name = "hors", about = "Instant coding answers via stackoverflow & hors.")]
+pub struct Args {
+    /// The query to search for, e.g. `hors "iterate over a vector rust"`.
+    pub query: Vec<String>,
+
+    /// Which search engine to scrape first: bing, duckduckgo, or google.
+    /// Falls back through the others if this one fails.
+    #[structopt(long = "search-engine", default_value = "bing")]
+    pub search_engine: String,
+
+    /// How many result pages to fetch and aggregate.
+    #[structopt(long = "pages", default_value = "1")]
+    pub pages: usize,
+
+    /// Extra Stack Exchange sites to search in addition to Stack Overflow,
+    /// e.g. `--site unix.stackexchange.com --site superuser.com`.
+    #[structopt(long = "site", number_of_values = 1)]
+    pub extra_sites: Vec<String>,
+
+    /// Skip the dead-link verification pass for raw speed.
+    #[structopt(long = "no-verify-links")]
+    pub no_verify_links: bool,
+}
+
+impl Args {
+    /// Resolve the `--search-engine` flag into an `Engine`, falling back to
+    /// the default (Bing) for an unrecognized name.
+    pub fn engine(&self) -> Engine {
+        Engine::from_name(&self.search_engine).unwrap_or_default()
+    }
+
+    /// Build the full list of allowed Stack Exchange sites: Stack Overflow
+    /// plus whatever extra `--site` flags were passed.
+    pub fn sites(&self) -> Vec<String> {
+        let mut sites = default_sites();
+        sites.extend(self.extra_sites.iter().cloned());
+        sites
+    }
+
+    /// Whether the dead-link verification pass should run.
+    pub fn verify_links(&self) -> bool {
+        !self.no_verify_links
+    }
+}
@@ -1,81 +1,76 @@
-use crate::error::{HorsError, Result};
+use crate::engine::{filter_links_by_site, site_query_fragment, SearchEngine};
+use crate::error::Result;
 use crate::utils::random_agent;
 use reqwest::RequestBuilder;
 use select::document::Document;
 use select::predicate::{Class, Name, Predicate};
 
+/// Number of results bing returns per search result page.
+const RESULTS_PER_PAGE: usize = 10;
+
 /// Search result links under the `bing` search engine.
-///
-/// This function will go through network to find out useful links in bing.
-///
-/// # Arguments
-///
-/// * `query` - The user input query String.
-///
-/// # Return value
-///
-/// If search links successfully, it will return a Vector of String, which indicate
-/// relative links to got answer.  Else return an Error.
-pub fn search_links(query: &String) -> Result<Vec<String>> {
-    let page: String = fetch(query)?;
-    let extract_results = extract_links(&page);
-    match extract_results {
-        Some(links) => return Ok(links),
-        None => {
-            return Err(HorsError::from_parse("Can't find search result..."));
+pub struct Bing;
+
+#[async_trait::async_trait]
+impl SearchEngine for Bing {
+    /// fetch actual page according to given query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The user input query String.
+    /// * `page` - Which (zero-indexed) results page to fetch.
+    /// * `sites` - Which Stack Exchange sites to restrict the query to.
+    ///
+    /// # Return value
+    ///
+    /// If get search result page successfully, it will return the content of page,
+    /// or returns error.
+    async fn fetch(&self, query: &str, page: usize, sites: &[String]) -> Result<String> {
+        let mut url: String = format!(
+            "https://www.bing.com/search?q={}%20{}",
+            site_query_fragment(sites),
+            query
+        );
+        if page > 0 {
+            url.push_str(&format!("&first={}", RESULTS_PER_PAGE * page + 1));
         }
+        let client = reqwest::Client::builder().cookie_store(true).build()?;
+        let request: RequestBuilder = client
+            .get(url.as_str())
+            .header(reqwest::header::USER_AGENT, random_agent());
+        debug!("Request to bing information: {:?}", request);
+        let res = request.send().await?;
+        let page: String = res.text().await?;
+        return Ok(page);
     }
-}
 
-/// fetch actual page according to given query.
-///
-/// # Arguments
-///
-/// * `query` - The user input query String.
-///
-/// # Return value
-///
-/// If get search result page successfully, it will return the content of page,
-/// or returns error.
-fn fetch(query: &String) -> Result<String> {
-    let url: String = format!(
-        "https://www.bing.com/search?q=site:stackoverflow.com%20{}",
-        query
-    );
-    let client = reqwest::ClientBuilder::new().cookie_store(true).build()?;
-    let request: RequestBuilder = client
-        .get(url.as_str())
-        .header(reqwest::header::USER_AGENT, random_agent());
-    debug!("Request to bing information: {:?}", request);
-    let mut res = request.send()?;
-    let page: String = res.text()?;
-    return Ok(page);
-}
-
-/// Extract links from given page.
-///
-/// # Arguments
-///
-/// * `page` - the bing search result page, which is mainly got by `fetch` function
-///
-/// # Return value
-///
-/// Links to the relative question, or returns None if we can't find it.
-fn extract_links(page: &String) -> Option<Vec<String>> {
-    let mut links: Vec<String> = Vec::new();
-    let doc: Document = Document::from(page.as_str());
-    let target_elements = doc.find(Class("b_algo").descendant(Name("h2")).descendant(Name("a")));
-    for node in target_elements {
-        if let Some(link) = node.attr("href") {
-            links.push(String::from(link));
+    /// Extract links from given page.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - the bing search result page, which is mainly got by `fetch` function
+    /// * `sites` - Which Stack Exchange sites are allowed in the results.
+    ///
+    /// # Return value
+    ///
+    /// Links to the relative question, or returns None if we can't find it.
+    fn extract_links(&self, page: &str, sites: &[String]) -> Option<Vec<String>> {
+        let mut links: Vec<String> = Vec::new();
+        let doc: Document = Document::from(page);
+        let target_elements = doc.find(Class("b_algo").descendant(Name("h2")).descendant(Name("a")));
+        for node in target_elements {
+            if let Some(link) = node.attr("href") {
+                links.push(String::from(link));
+            }
         }
-    }
 
-    debug!("Links extract from bing: {:?}", links);
-    if links.len() == 0 {
-        return None;
+        let links = filter_links_by_site(links, sites);
+        debug!("Links extract from bing: {:?}", links);
+        if links.len() == 0 {
+            return None;
+        }
+        return Some(links);
     }
-    return Some(links);
 }
 
 #[cfg(test)]
@@ -89,29 +84,57 @@ mod tests {
 <html>
     <body>
         <li class=\"b_algo\">
-            <h2><a target=\"_blank\" href=\"https://test_link1\"></a></h2>
+            <h2><a target=\"_blank\" href=\"https://stackoverflow.com/questions/1\"></a></h2>
         </li>
         <li class=\"b_algo\">
-            <h2><a target=\"_blank\" href=\"https://test_link2\"></a></h2>
+            <h2><a target=\"_blank\" href=\"https://unix.stackexchange.com/questions/2\"></a></h2>
         </li>
     </body>
 </html>",
         );
-        let possible_links: Option<Vec<String>> = extract_links(&page);
+        let sites = vec![
+            String::from("stackoverflow.com"),
+            String::from("unix.stackexchange.com"),
+        ];
+        let possible_links: Option<Vec<String>> = Bing.extract_links(&page, &sites);
         assert_eq!(possible_links.is_some(), true);
         assert_eq!(
             possible_links.unwrap(),
             vec![
-                String::from("https://test_link1"),
-                String::from("https://test_link2")
+                String::from("https://stackoverflow.com/questions/1"),
+                String::from("https://unix.stackexchange.com/questions/2")
             ]
         )
     }
 
+    #[test]
+    fn test_extract_links_filters_out_disallowed_sites() {
+        let page: String = String::from(
+            "
+<html>
+    <body>
+        <li class=\"b_algo\">
+            <h2><a target=\"_blank\" href=\"https://stackoverflow.com/questions/1\"></a></h2>
+        </li>
+        <li class=\"b_algo\">
+            <h2><a target=\"_blank\" href=\"https://superuser.com/questions/2\"></a></h2>
+        </li>
+    </body>
+</html>",
+        );
+        let sites = vec![String::from("stackoverflow.com")];
+        let possible_links: Option<Vec<String>> = Bing.extract_links(&page, &sites);
+        assert_eq!(
+            possible_links.unwrap(),
+            vec![String::from("https://stackoverflow.com/questions/1")]
+        )
+    }
+
     #[test]
     fn test_extract_links_when_there_are_no_links_available() {
         let page: String = String::from("<html></html>");
-        let possible_links: Option<Vec<String>> = extract_links(&page);
+        let sites = vec![String::from("stackoverflow.com")];
+        let possible_links: Option<Vec<String>> = Bing.extract_links(&page, &sites);
         assert_eq!(possible_links.is_none(), true);
     }
 }
@@ -0,0 +1,150 @@
+use crate::engine::{filter_links_by_site, site_query_fragment, SearchEngine};
+use crate::error::Result;
+use crate::utils::random_agent;
+use reqwest::RequestBuilder;
+use select::document::Document;
+use select::predicate::Class;
+
+/// Number of results duckduckgo returns per search result page.
+const RESULTS_PER_PAGE: usize = 30;
+
+/// Search result links under the `duckduckgo` search engine.
+pub struct DuckDuckGo;
+
+#[async_trait::async_trait]
+impl SearchEngine for DuckDuckGo {
+    /// fetch actual page according to given query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The user input query String.
+    /// * `page` - Which (zero-indexed) results page to fetch.
+    /// * `sites` - Which Stack Exchange sites to restrict the query to.
+    ///
+    /// # Return value
+    ///
+    /// If get search result page successfully, it will return the content of page,
+    /// or returns error.
+    async fn fetch(&self, query: &str, page: usize, sites: &[String]) -> Result<String> {
+        let mut url: String = format!(
+            "https://duckduckgo.com/html/?q={}%20{}",
+            site_query_fragment(sites),
+            query
+        );
+        if page > 0 {
+            url.push_str(&format!("&s={}", RESULTS_PER_PAGE * page));
+        }
+        let client = reqwest::Client::builder().cookie_store(true).build()?;
+        let request: RequestBuilder = client
+            .get(url.as_str())
+            .header(reqwest::header::USER_AGENT, random_agent());
+        debug!("Request to duckduckgo information: {:?}", request);
+        let res = request.send().await?;
+        let page: String = res.text().await?;
+        return Ok(page);
+    }
+
+    /// Extract links from given page.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - the duckduckgo search result page, which is mainly got by `fetch` function
+    /// * `sites` - Which Stack Exchange sites are allowed in the results.
+    ///
+    /// # Return value
+    ///
+    /// Links to the relative question, or returns None if we can't find it.
+    fn extract_links(&self, page: &str, sites: &[String]) -> Option<Vec<String>> {
+        let mut links: Vec<String> = Vec::new();
+        let doc: Document = Document::from(page);
+        let target_elements = doc.find(Class("result__a"));
+        for node in target_elements {
+            if let Some(href) = node.attr("href") {
+                if let Some(link) = extract_uddg(href) {
+                    links.push(link);
+                }
+            }
+        }
+
+        let links = filter_links_by_site(links, sites);
+        debug!("Links extract from duckduckgo: {:?}", links);
+        if links.len() == 0 {
+            return None;
+        }
+        return Some(links);
+    }
+}
+
+/// DuckDuckGo's html result anchors wrap the real URL percent-encoded
+/// behind a `uddg=` redirect parameter, e.g.
+/// `//duckduckgo.com/l/?uddg=https%3A%2F%2Fstackoverflow.com%2F...&rut=...`.
+/// Pull the real URL back out of that wrapper.
+fn extract_uddg(href: &str) -> Option<String> {
+    let marker = "uddg=";
+    let start = href.find(marker)? + marker.len();
+    let rest = &href[start..];
+    let end = rest.find('&').unwrap_or(rest.len());
+    let encoded = &rest[..end];
+    percent_encoding::percent_decode_str(encoded)
+        .decode_utf8()
+        .ok()
+        .map(|s| s.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links() {
+        let page: String = String::from(
+            "
+<html>
+    <body>
+        <a class=\"result__a\" href=\"//duckduckgo.com/l/?uddg=https%3A%2F%2Fstackoverflow.com%2Fquestions%2F1&rut=1\"></a>
+        <a class=\"result__a\" href=\"//duckduckgo.com/l/?uddg=https%3A%2F%2Funix.stackexchange.com%2Fquestions%2F2&rut=2\"></a>
+    </body>
+</html>",
+        );
+        let sites = vec![
+            String::from("stackoverflow.com"),
+            String::from("unix.stackexchange.com"),
+        ];
+        let possible_links: Option<Vec<String>> = DuckDuckGo.extract_links(&page, &sites);
+        assert_eq!(possible_links.is_some(), true);
+        assert_eq!(
+            possible_links.unwrap(),
+            vec![
+                String::from("https://stackoverflow.com/questions/1"),
+                String::from("https://unix.stackexchange.com/questions/2")
+            ]
+        )
+    }
+
+    #[test]
+    fn test_extract_links_filters_out_disallowed_sites() {
+        let page: String = String::from(
+            "
+<html>
+    <body>
+        <a class=\"result__a\" href=\"//duckduckgo.com/l/?uddg=https%3A%2F%2Fstackoverflow.com%2Fquestions%2F1&rut=1\"></a>
+        <a class=\"result__a\" href=\"//duckduckgo.com/l/?uddg=https%3A%2F%2Fsuperuser.com%2Fquestions%2F2&rut=2\"></a>
+    </body>
+</html>",
+        );
+        let sites = vec![String::from("stackoverflow.com")];
+        let possible_links: Option<Vec<String>> = DuckDuckGo.extract_links(&page, &sites);
+        assert_eq!(
+            possible_links.unwrap(),
+            vec![String::from("https://stackoverflow.com/questions/1")]
+        )
+    }
+
+    #[test]
+    fn test_extract_links_when_there_are_no_links_available() {
+        let page: String = String::from("<html></html>");
+        let sites = vec![String::from("stackoverflow.com")];
+        let possible_links: Option<Vec<String>> = DuckDuckGo.extract_links(&page, &sites);
+        assert_eq!(possible_links.is_none(), true);
+    }
+}
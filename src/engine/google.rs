@@ -0,0 +1,152 @@
+use crate::engine::{filter_links_by_site, site_query_fragment, SearchEngine};
+use crate::error::Result;
+use crate::utils::random_agent;
+use reqwest::RequestBuilder;
+use select::document::Document;
+use select::predicate::Name;
+
+/// Number of results google returns per search result page.
+const RESULTS_PER_PAGE: usize = 10;
+
+/// Search result links under the `google` search engine.
+pub struct Google;
+
+#[async_trait::async_trait]
+impl SearchEngine for Google {
+    /// fetch actual page according to given query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The user input query String.
+    /// * `page` - Which (zero-indexed) results page to fetch.
+    /// * `sites` - Which Stack Exchange sites to restrict the query to.
+    ///
+    /// # Return value
+    ///
+    /// If get search result page successfully, it will return the content of page,
+    /// or returns error.
+    async fn fetch(&self, query: &str, page: usize, sites: &[String]) -> Result<String> {
+        let mut url: String = format!(
+            "https://www.google.com/search?q={}%20{}",
+            site_query_fragment(sites),
+            query
+        );
+        if page > 0 {
+            url.push_str(&format!("&start={}", RESULTS_PER_PAGE * page));
+        }
+        let client = reqwest::Client::builder().cookie_store(true).build()?;
+        let request: RequestBuilder = client
+            .get(url.as_str())
+            .header(reqwest::header::USER_AGENT, random_agent());
+        debug!("Request to google information: {:?}", request);
+        let res = request.send().await?;
+        let page: String = res.text().await?;
+        return Ok(page);
+    }
+
+    /// Extract links from given page.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - the google search result page, which is mainly got by `fetch` function
+    /// * `sites` - Which Stack Exchange sites are allowed in the results.
+    ///
+    /// # Return value
+    ///
+    /// Links to the relative question, or returns None if we can't find it.
+    fn extract_links(&self, page: &str, sites: &[String]) -> Option<Vec<String>> {
+        let mut links: Vec<String> = Vec::new();
+        let doc: Document = Document::from(page);
+        let target_elements = doc.find(Name("a"));
+        for node in target_elements {
+            if let Some(href) = node.attr("href") {
+                if let Some(link) = extract_google_url(href) {
+                    links.push(link);
+                }
+            }
+        }
+
+        let links = filter_links_by_site(links, sites);
+        debug!("Links extract from google: {:?}", links);
+        if links.len() == 0 {
+            return None;
+        }
+        return Some(links);
+    }
+}
+
+/// Google wraps the real URL behind a `/url?q=` redirect on its result
+/// anchors, e.g. `/url?q=https://stackoverflow.com/...&sa=U&ved=...`.
+/// Pull the real URL back out of that wrapper.
+fn extract_google_url(href: &str) -> Option<String> {
+    let marker = "/url?q=";
+    if !href.starts_with(marker) {
+        return None;
+    }
+    let rest = &href[marker.len()..];
+    let end = rest.find('&').unwrap_or(rest.len());
+    let encoded = &rest[..end];
+    percent_encoding::percent_decode_str(encoded)
+        .decode_utf8()
+        .ok()
+        .map(|s| s.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links() {
+        let page: String = String::from(
+            "
+<html>
+    <body>
+        <a href=\"/url?q=https://stackoverflow.com/questions/1&sa=U&ved=1\"></a>
+        <a href=\"/url?q=https://unix.stackexchange.com/questions/2&sa=U&ved=2\"></a>
+        <a href=\"/search?q=something+else\"></a>
+    </body>
+</html>",
+        );
+        let sites = vec![
+            String::from("stackoverflow.com"),
+            String::from("unix.stackexchange.com"),
+        ];
+        let possible_links: Option<Vec<String>> = Google.extract_links(&page, &sites);
+        assert_eq!(possible_links.is_some(), true);
+        assert_eq!(
+            possible_links.unwrap(),
+            vec![
+                String::from("https://stackoverflow.com/questions/1"),
+                String::from("https://unix.stackexchange.com/questions/2")
+            ]
+        )
+    }
+
+    #[test]
+    fn test_extract_links_filters_out_disallowed_sites() {
+        let page: String = String::from(
+            "
+<html>
+    <body>
+        <a href=\"/url?q=https://stackoverflow.com/questions/1&sa=U&ved=1\"></a>
+        <a href=\"/url?q=https://superuser.com/questions/2&sa=U&ved=2\"></a>
+    </body>
+</html>",
+        );
+        let sites = vec![String::from("stackoverflow.com")];
+        let possible_links: Option<Vec<String>> = Google.extract_links(&page, &sites);
+        assert_eq!(
+            possible_links.unwrap(),
+            vec![String::from("https://stackoverflow.com/questions/1")]
+        )
+    }
+
+    #[test]
+    fn test_extract_links_when_there_are_no_links_available() {
+        let page: String = String::from("<html></html>");
+        let sites = vec![String::from("stackoverflow.com")];
+        let possible_links: Option<Vec<String>> = Google.extract_links(&page, &sites);
+        assert_eq!(possible_links.is_none(), true);
+    }
+}
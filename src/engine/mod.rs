@@ -0,0 +1,465 @@
+use crate::error::{HorsError, Result};
+use crate::utils::random_agent;
+use futures::stream::{self, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::time::sleep;
+
+pub mod bing;
+pub mod duckduckgo;
+pub mod google;
+
+/// Default cap on how many question pages are fetched concurrently once
+/// `search_links` has found candidate links, so a large result set doesn't
+/// hammer the target server with unbounded parallel requests.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How many times a single engine is retried (with backoff) before we give
+/// up on it and fall through to the next engine in the chain.
+const MAX_RETRIES_PER_ENGINE: u32 = 2;
+
+/// Engines tried in order when the primary one keeps failing.
+const FALLBACK_CHAIN: [Engine; 3] = [Engine::Bing, Engine::DuckDuckGo, Engine::Google];
+
+/// The search engines `hors` knows how to scrape for links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    Bing,
+    DuckDuckGo,
+    Google,
+}
+
+impl Engine {
+    /// Parse an engine name coming from the CLI/config (case-insensitive).
+    ///
+    /// Returns `None` for anything that isn't a recognized engine, so
+    /// callers can fall back to the default.
+    pub fn from_name(name: &str) -> Option<Engine> {
+        match name.to_lowercase().as_str() {
+            "bing" => Some(Engine::Bing),
+            "duckduckgo" | "ddg" => Some(Engine::DuckDuckGo),
+            "google" => Some(Engine::Google),
+            _ => None,
+        }
+    }
+
+    fn instance(&self) -> Box<dyn SearchEngine> {
+        match self {
+            Engine::Bing => Box::new(bing::Bing),
+            Engine::DuckDuckGo => Box::new(duckduckgo::DuckDuckGo),
+            Engine::Google => Box::new(google::Google),
+        }
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Engine {
+        Engine::Bing
+    }
+}
+
+/// A pluggable search backend capable of turning a user query into a list
+/// of candidate Stack Overflow question links.
+///
+/// Each engine owns its own request URL template and markup selectors, so
+/// adding a new engine never touches the others, and a single engine going
+/// down (changed markup, a block page, ...) doesn't take the whole crate
+/// with it.
+#[async_trait::async_trait]
+pub trait SearchEngine: Send + Sync {
+    /// Fetch the raw search result page for `query`, restricted to results
+    /// from `sites`.
+    async fn fetch(&self, query: &str, page: usize, sites: &[String]) -> Result<String>;
+
+    /// Pull the question links out of a page previously returned by `fetch`,
+    /// keeping only the ones hosted on one of `sites`.
+    fn extract_links(&self, page: &str, sites: &[String]) -> Option<Vec<String>>;
+
+    /// Fetch a results page and extract links from it in one go.
+    async fn search_links(
+        &self,
+        query: &str,
+        page: usize,
+        sites: &[String],
+    ) -> Result<Vec<String>> {
+        let content = self.fetch(query, page, sites).await?;
+        match self.extract_links(&content, sites) {
+            Some(links) => Ok(links),
+            None => Err(HorsError::from_parse("Can't find search result...")),
+        }
+    }
+}
+
+/// Default list of allowed Stack Exchange sites: just Stack Overflow, for
+/// backward compatibility with earlier `hors` behavior.
+pub fn default_sites() -> Vec<String> {
+    vec![String::from("stackoverflow.com")]
+}
+
+/// Build the grouped `site:` query fragment for the given allowed sites,
+/// e.g. `(site:stackoverflow.com OR site:unix.stackexchange.com)` for more
+/// than one site, or a bare `site:stackoverflow.com` for just one.
+pub fn site_query_fragment(sites: &[String]) -> String {
+    if sites.len() <= 1 {
+        return sites
+            .get(0)
+            .map(|site| format!("site:{}", site))
+            .unwrap_or_default();
+    }
+    let clauses: Vec<String> = sites.iter().map(|site| format!("site:{}", site)).collect();
+    format!("({})", clauses.join(" OR "))
+}
+
+/// Keep only links whose host matches one of the allowed sites (or one of
+/// their subdomains).
+pub(crate) fn filter_links_by_site(links: Vec<String>, sites: &[String]) -> Vec<String> {
+    links
+        .into_iter()
+        .filter(|link| host_matches_site(link, sites))
+        .collect()
+}
+
+fn host_matches_site(link: &str, sites: &[String]) -> bool {
+    let without_scheme = link.splitn(2, "://").nth(1).unwrap_or(link);
+    let host = without_scheme.split('/').next().unwrap_or(without_scheme);
+    sites
+        .iter()
+        .any(|site| host == site.as_str() || host.ends_with(&format!(".{}", site)))
+}
+
+/// Search links for `query` using the given `engine`.
+///
+/// # Arguments
+///
+/// * `query` - The user input query String.
+/// * `engine` - Which search engine to scrape, picked through the
+///   `--search-engine`/config option.
+/// * `pages` - How many result pages to fetch, starting from the first one.
+///   Links from every page are aggregated into a single deduplicated Vec,
+///   in the order they were found.
+/// * `sites` - Which Stack Exchange sites to restrict the query to, e.g.
+///   `["stackoverflow.com", "unix.stackexchange.com"]`. Use
+///   [`default_sites`] for the historical Stack Overflow-only behavior.
+/// * `verify` - When true, drop any extracted link that doesn't resolve to
+///   a reachable (2xx) page before returning. Costs an extra round trip per
+///   link, so callers who want raw speed can turn it off.
+pub async fn search_links(
+    query: &String,
+    engine: Engine,
+    pages: usize,
+    sites: &[String],
+    verify: bool,
+) -> Result<Vec<String>> {
+    let instance = engine.instance();
+    let mut links: Vec<String> = Vec::new();
+    for page in 0..pages.max(1) {
+        let result = instance.search_links(query, page, sites).await;
+        if !merge_page_result(&mut links, page, result)? {
+            break;
+        }
+    }
+    if verify {
+        links = verify_links(links, DEFAULT_CONCURRENCY).await;
+    }
+    Ok(links)
+}
+
+/// Merge a single page's fetch result into the `links` accumulated so far,
+/// deduplicating and preserving discovery order.
+///
+/// Returns `Ok(true)` to keep fetching subsequent pages, `Ok(false)` to stop
+/// aggregating without error (a later page ran out, e.g. we went past the
+/// last page of results), or `Err` to propagate immediately (the very first
+/// page failing is a real error, not just "no more pages").
+fn merge_page_result(
+    links: &mut Vec<String>,
+    page: usize,
+    result: Result<Vec<String>>,
+) -> Result<bool> {
+    match result {
+        Ok(page_links) => {
+            for link in page_links {
+                if !links.contains(&link) {
+                    links.push(link);
+                }
+            }
+            Ok(true)
+        }
+        Err(err) => {
+            if page == 0 {
+                return Err(err);
+            }
+            debug!("Failed fetching page {}: {:?}", page, err);
+            Ok(false)
+        }
+    }
+}
+
+/// Drop any of `links` that don't resolve to a reachable (2xx) page,
+/// checking all of them concurrently bounded by `concurrency`.
+pub async fn verify_links(links: Vec<String>, concurrency: usize) -> Vec<String> {
+    let concurrency = concurrency.max(1);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    stream::iter(links.into_iter().map(|link| {
+        let semaphore = Arc::clone(&semaphore);
+        async move {
+            let _permit = semaphore.acquire().await;
+            let alive = link_is_alive(&link).await;
+            (link, alive)
+        }
+    }))
+    .buffered(concurrency)
+    .collect::<Vec<(String, bool)>>()
+    .await
+    .into_iter()
+    .filter_map(|(link, alive)| if alive { Some(link) } else { None })
+    .collect()
+}
+
+/// Check whether `link` resolves to a reachable (2xx) page, preferring a
+/// lightweight `HEAD` request and falling back to `GET` for servers that
+/// don't support `HEAD`.
+async fn link_is_alive(link: &str) -> bool {
+    let client = match reqwest::Client::builder().cookie_store(true).build() {
+        Ok(client) => client,
+        Err(_) => return false,
+    };
+
+    let head_request = client
+        .head(link)
+        .header(reqwest::header::USER_AGENT, random_agent());
+    if let Ok(res) = head_request.send().await {
+        if res.status().is_success() {
+            return true;
+        }
+    }
+
+    let get_request = client
+        .get(link)
+        .header(reqwest::header::USER_AGENT, random_agent());
+    match get_request.send().await {
+        Ok(res) => res.status().is_success(),
+        Err(_) => false,
+    }
+}
+
+/// Fetch each of `links` concurrently, bounded by `concurrency` in-flight
+/// requests at a time, returning the page content (or error) for every link
+/// in the same order it was given.
+///
+/// # Arguments
+///
+/// * `links` - The candidate question links previously returned by
+///   `search_links`.
+/// * `concurrency` - Maximum number of requests in flight at once; use
+///   [`DEFAULT_CONCURRENCY`] unless the caller has a reason to change it.
+pub async fn fetch_pages_concurrently(
+    links: Vec<String>,
+    concurrency: usize,
+) -> Vec<Result<String>> {
+    let concurrency = concurrency.max(1);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    stream::iter(links.into_iter().map(|link| {
+        let semaphore = Arc::clone(&semaphore);
+        async move {
+            let _permit = semaphore.acquire().await;
+            fetch_page(&link).await
+        }
+    }))
+    .buffered(concurrency)
+    .collect()
+    .await
+}
+
+/// Fetch a single question page by its URL.
+async fn fetch_page(link: &str) -> Result<String> {
+    let client = reqwest::Client::builder().cookie_store(true).build()?;
+    let request = client
+        .get(link)
+        .header(reqwest::header::USER_AGENT, random_agent());
+    debug!("Fetching question page: {:?}", request);
+    let res = request.send().await?;
+    let page = res.text().await?;
+    Ok(page)
+}
+
+/// Search links for `query`, trying `engine` first and falling back through
+/// the rest of the engine chain (Bing -> DuckDuckGo -> Google, skipping
+/// `engine` itself since it was already tried) if it keeps failing.
+///
+/// A single scrape can fail outright when an engine serves a CAPTCHA, an
+/// empty results page, or hits a network error. Each engine gets a few
+/// retries with exponential backoff (and a freshly rotated user agent on
+/// every attempt, since `random_agent()` is re-picked per request) before we
+/// give up on it and move to the next one.
+///
+/// # Arguments
+///
+/// * `query` - The user input query String.
+/// * `engine` - The preferred engine to try first.
+/// * `pages`, `sites`, `verify` - See [`search_links`].
+pub async fn search_links_with_fallback(
+    query: &String,
+    engine: Engine,
+    pages: usize,
+    sites: &[String],
+    verify: bool,
+) -> Result<Vec<String>> {
+    let mut last_err: Option<HorsError> = None;
+    for candidate in build_fallback_chain(engine) {
+        match search_links_with_retry(query, candidate, pages, sites, verify).await {
+            Ok(links) => return Ok(links),
+            Err(err) => {
+                debug!("Engine {:?} failed, trying next engine: {:?}", candidate, err);
+                last_err = Some(err);
+            }
+        }
+    }
+    Err(last_err.unwrap_or_else(|| HorsError::from_parse("Can't find search result...")))
+}
+
+/// Build the ordered list of engines to try, starting with `primary` and
+/// then falling through the rest of [`FALLBACK_CHAIN`] in its fixed
+/// Bing -> DuckDuckGo -> Google order, skipping `primary` itself so it's
+/// never tried twice.
+fn build_fallback_chain(primary: Engine) -> Vec<Engine> {
+    let mut chain: Vec<Engine> = vec![primary];
+    chain.extend(FALLBACK_CHAIN.iter().copied().filter(|e| *e != primary));
+    chain
+}
+
+/// Retry a single engine with exponential backoff before giving up on it.
+async fn search_links_with_retry(
+    query: &String,
+    engine: Engine,
+    pages: usize,
+    sites: &[String],
+    verify: bool,
+) -> Result<Vec<String>> {
+    let mut attempt = 0;
+    loop {
+        match search_links(query, engine, pages, sites, verify).await {
+            Ok(links) => return Ok(links),
+            Err(err) => {
+                attempt += 1;
+                if attempt > MAX_RETRIES_PER_ENGINE {
+                    return Err(err);
+                }
+                let backoff = Duration::from_millis(200 * 2u64.pow(attempt - 1));
+                debug!(
+                    "Attempt {} for engine {:?} failed ({:?}), retrying in {:?}",
+                    attempt, engine, err, backoff
+                );
+                sleep(backoff).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_site_query_fragment_with_one_site() {
+        let sites = vec![String::from("stackoverflow.com")];
+        assert_eq!(site_query_fragment(&sites), "site:stackoverflow.com");
+    }
+
+    #[test]
+    fn test_site_query_fragment_groups_multiple_sites_with_or() {
+        let sites = vec![
+            String::from("stackoverflow.com"),
+            String::from("unix.stackexchange.com"),
+        ];
+        assert_eq!(
+            site_query_fragment(&sites),
+            "(site:stackoverflow.com OR site:unix.stackexchange.com)"
+        );
+    }
+
+    #[test]
+    fn test_host_matches_site_accepts_exact_host_and_subdomains() {
+        let sites = vec![String::from("stackexchange.com")];
+        assert_eq!(
+            host_matches_site("https://unix.stackexchange.com/questions/1", &sites),
+            true
+        );
+        assert_eq!(
+            host_matches_site("https://stackexchange.com/questions/1", &sites),
+            true
+        );
+    }
+
+    #[test]
+    fn test_host_matches_site_rejects_unrelated_hosts() {
+        let sites = vec![String::from("stackoverflow.com")];
+        assert_eq!(
+            host_matches_site("https://superuser.com/questions/1", &sites),
+            false
+        );
+        assert_eq!(
+            host_matches_site("https://notstackoverflow.com/questions/1", &sites),
+            false
+        );
+    }
+
+    #[test]
+    fn test_merge_page_result_dedups_and_preserves_order() {
+        let mut links = vec![String::from("https://a")];
+        let continue_fetching = merge_page_result(
+            &mut links,
+            1,
+            Ok(vec![String::from("https://a"), String::from("https://b")]),
+        )
+        .unwrap();
+        assert_eq!(continue_fetching, true);
+        assert_eq!(
+            links,
+            vec![String::from("https://a"), String::from("https://b")]
+        );
+    }
+
+    #[test]
+    fn test_merge_page_result_propagates_error_on_first_page() {
+        let mut links: Vec<String> = Vec::new();
+        let result = merge_page_result(&mut links, 0, Err(HorsError::from_parse("boom")));
+        assert_eq!(result.is_err(), true);
+    }
+
+    #[test]
+    fn test_merge_page_result_stops_without_error_on_later_page() {
+        let mut links = vec![String::from("https://a")];
+        let continue_fetching =
+            merge_page_result(&mut links, 1, Err(HorsError::from_parse("boom"))).unwrap();
+        assert_eq!(continue_fetching, false);
+        assert_eq!(links, vec![String::from("https://a")]);
+    }
+
+    #[test]
+    fn test_build_fallback_chain_never_duplicates_the_primary_engine() {
+        for primary in [Engine::Bing, Engine::DuckDuckGo, Engine::Google] {
+            let chain = build_fallback_chain(primary);
+            assert_eq!(chain.len(), 3);
+            assert_eq!(chain[0], primary);
+            assert_eq!(chain.iter().filter(|e| **e == primary).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_build_fallback_chain_preserves_bing_duckduckgo_google_ordering() {
+        assert_eq!(
+            build_fallback_chain(Engine::Bing),
+            vec![Engine::Bing, Engine::DuckDuckGo, Engine::Google]
+        );
+        assert_eq!(
+            build_fallback_chain(Engine::DuckDuckGo),
+            vec![Engine::DuckDuckGo, Engine::Bing, Engine::Google]
+        );
+        assert_eq!(
+            build_fallback_chain(Engine::Google),
+            vec![Engine::Google, Engine::Bing, Engine::DuckDuckGo]
+        );
+    }
+}
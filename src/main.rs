@@ -0,0 +1,52 @@
+#[macro_use]
+extern crate log;
+
+mod args;
+mod engine;
+mod error;
+mod utils;
+
+use args::Args;
+use structopt::StructOpt;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let args = Args::from_args();
+    let query = args.query.join(" ");
+    let sites = args.sites();
+
+    let result = engine::search_links_with_fallback(
+        &query,
+        args.engine(),
+        args.pages,
+        &sites,
+        args.verify_links(),
+    )
+    .await;
+
+    match result {
+        Ok(links) => {
+            if links.is_empty() {
+                eprintln!("hors: no results found");
+                std::process::exit(1);
+            }
+            // Fetch every candidate question page concurrently (bounded),
+            // rather than one at a time, since `links` can hold several
+            // pages' worth of results once `--pages` is greater than one.
+            let pages =
+                engine::fetch_pages_concurrently(links.clone(), engine::DEFAULT_CONCURRENCY)
+                    .await;
+            for (link, page) in links.iter().zip(pages.into_iter()) {
+                match page {
+                    Ok(content) => println!("{} ({} bytes)", link, content.len()),
+                    Err(err) => eprintln!("hors: failed to fetch {}: {}", link, err),
+                }
+            }
+        }
+        Err(err) => {
+            eprintln!("hors: {}", err);
+            std::process::exit(1);
+        }
+    }
+}